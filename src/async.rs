@@ -0,0 +1,80 @@
+//! Asynchronous RIS Live session exposed as a [`futures::Stream`].
+//!
+//! This mirrors the blocking [`RISLiveSession`](crate::RISLiveSession), but
+//! instead of a `read_msg` loop it implements `Stream<Item = Result<BGPUpdate,
+//! RisError>>`: text frames are read off a `tokio-tungstenite` socket,
+//! deserialized to [`RISPacket`], run through [`BGPUpdate::from_message`], and
+//! the resulting `Vec<BGPUpdate>` is flattened into the stream. Callers can
+//! then `while let Some(update) = stream.next().await` and compose with
+//! `select!`, timeouts and backpressure.
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{ Context, Poll };
+
+use futures::{ Stream, StreamExt, SinkExt };
+use tokio::net::TcpStream;
+use tokio_tungstenite::{ connect_async, MaybeTlsStream, WebSocketStream };
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::{ RIS_URL, RisError, BGPUpdate, SubscriptionFilter };
+use crate::parse::RISPacket;
+
+/// An asynchronous RIS Live session.
+pub struct AsyncRISLiveSession {
+    sock: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    /// Updates decoded from the most recent frame that have not yet been
+    /// yielded (a single frame can expand to several [`BGPUpdate`]s).
+    buffer: VecDeque<BGPUpdate>,
+}
+impl AsyncRISLiveSession {
+    /// Connect to RIS Live.
+    pub async fn new() -> Result<Self, RisError> {
+        let (sock, _) = connect_async(RIS_URL).await
+            .map_err(RisError::Connect)?;
+        Ok(Self { sock, buffer: VecDeque::new() })
+    }
+
+    /// Push a [`SubscriptionFilter`] to the server.
+    pub async fn subscribe(&mut self, filter: &SubscriptionFilter)
+        -> Result<(), RisError>
+    {
+        let payload = serde_json::json!({
+            "type": "ris_subscribe",
+            "data": filter,
+        }).to_string();
+        self.sock.send(Message::Text(payload)).await?;
+        Ok(())
+    }
+}
+impl Stream for AsyncRISLiveSession {
+    type Item = Result<BGPUpdate, RisError>;
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>)
+        -> Poll<Option<Self::Item>>
+    {
+        loop {
+            if let Some(update) = self.buffer.pop_front() {
+                return Poll::Ready(Some(Ok(update)));
+            }
+            match self.sock.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(Message::Text(s)))) => {
+                    match serde_json::from_str::<RISPacket>(&s) {
+                        Ok(RISPacket::Message(m)) => {
+                            if let Some(updates) = BGPUpdate::from_message(&m) {
+                                self.buffer.extend(updates);
+                            }
+                        },
+                        Err(e) => return Poll::Ready(Some(Err(e.into()))),
+                    }
+                    // Loop around to drain the buffer (or keep reading if the
+                    // frame produced no updates).
+                },
+                // Ignore non-text frames (pings, binary, &c.) and keep reading.
+                Poll::Ready(Some(Ok(_))) => {},
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e.into()))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
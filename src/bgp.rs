@@ -0,0 +1,124 @@
+//! A minimal decoder for raw BGP PDU bytes.
+//!
+//! RIS Live can attach the on-the-wire BGP message to each update when a
+//! subscription is made with `socketOptions.includeRaw` (see
+//! [`SubscriptionFilter::include_raw`](crate::SubscriptionFilter::include_raw)).
+//! This gives a ground-truth binary decode path that doesn't depend on RIS
+//! Live's pre-parsed JSON.
+
+use std::net::Ipv4Addr;
+
+/// Errors produced while decoding a raw BGP PDU.
+#[derive(Debug)]
+pub enum BgpDecodeError {
+    /// The hex string was not valid hex or had an odd length.
+    BadHex,
+    /// The PDU was shorter than the field being read.
+    Truncated,
+    /// The message type byte was not one of the four RFC 4271 types.
+    BadMessageType(u8),
+}
+impl std::fmt::Display for BgpDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BgpDecodeError::BadHex => write!(f, "invalid hex in raw field"),
+            BgpDecodeError::Truncated => write!(f, "raw PDU truncated"),
+            BgpDecodeError::BadMessageType(t) =>
+                write!(f, "unknown BGP message type {}", t),
+        }
+    }
+}
+impl std::error::Error for BgpDecodeError {}
+
+/// A decoded BGP PDU. UPDATE bodies are left as section lengths rather than
+/// fully parsing path attributes, which the JSON side already exposes.
+#[derive(Debug)]
+pub enum BgpPdu {
+    Open {
+        version: u8,
+        my_as: u16,
+        hold_time: u16,
+        bgp_id: Ipv4Addr,
+    },
+    Update {
+        withdrawn_routes_len: u16,
+        total_path_attribute_len: u16,
+    },
+    Notification {
+        error_code: u8,
+        error_subcode: u8,
+    },
+    Keepalive,
+}
+
+/// Decode a BGP PDU from its hex representation.
+pub fn decode_hex(hex: &str) -> Result<BgpPdu, BgpDecodeError> {
+    decode(&from_hex(hex)?)
+}
+
+/// Decode a BGP PDU from raw bytes, skipping the 16-byte marker.
+pub fn decode(buf: &[u8]) -> Result<BgpPdu, BgpDecodeError> {
+    // 16-byte marker, 2-byte length, 1-byte type.
+    if buf.len() < 19 {
+        return Err(BgpDecodeError::Truncated);
+    }
+    let msg_type = buf[18];
+    let body = &buf[19..];
+    match msg_type {
+        1 => {
+            // version(1) my_as(2) hold_time(2) bgp_id(4) ...
+            if body.len() < 9 {
+                return Err(BgpDecodeError::Truncated);
+            }
+            Ok(BgpPdu::Open {
+                version: body[0],
+                my_as: u16::from_be_bytes([body[1], body[2]]),
+                hold_time: u16::from_be_bytes([body[3], body[4]]),
+                bgp_id: Ipv4Addr::new(body[5], body[6], body[7], body[8]),
+            })
+        },
+        2 => {
+            if body.len() < 2 {
+                return Err(BgpDecodeError::Truncated);
+            }
+            let withdrawn_routes_len = u16::from_be_bytes([body[0], body[1]]);
+            let attr_off = 2 + withdrawn_routes_len as usize;
+            if body.len() < attr_off + 2 {
+                return Err(BgpDecodeError::Truncated);
+            }
+            let total_path_attribute_len =
+                u16::from_be_bytes([body[attr_off], body[attr_off + 1]]);
+            Ok(BgpPdu::Update { withdrawn_routes_len, total_path_attribute_len })
+        },
+        3 => {
+            if body.len() < 2 {
+                return Err(BgpDecodeError::Truncated);
+            }
+            Ok(BgpPdu::Notification {
+                error_code: body[0],
+                error_subcode: body[1],
+            })
+        },
+        4 => Ok(BgpPdu::Keepalive),
+        t => Err(BgpDecodeError::BadMessageType(t)),
+    }
+}
+
+/// Decode an even-length ASCII hex string into bytes.
+fn from_hex(hex: &str) -> Result<Vec<u8>, BgpDecodeError> {
+    let hex = hex.as_bytes();
+    if hex.len() % 2 != 0 {
+        return Err(BgpDecodeError::BadHex);
+    }
+    let nibble = |b: u8| -> Result<u8, BgpDecodeError> {
+        match b {
+            b'0'..=b'9' => Ok(b - b'0'),
+            b'a'..=b'f' => Ok(b - b'a' + 10),
+            b'A'..=b'F' => Ok(b - b'A' + 10),
+            _ => Err(BgpDecodeError::BadHex),
+        }
+    };
+    hex.chunks(2)
+        .map(|pair| Ok((nibble(pair[0])? << 4) | nibble(pair[1])?))
+        .collect()
+}
@@ -0,0 +1,96 @@
+//! Multi-consumer broadcast fan-out over a single upstream connection.
+//!
+//! Opening one [`AsyncRISLiveSession`] per consumer wastes collector
+//! connections and duplicates the firehose. A [`BroadcastHub`] owns the single
+//! socket, runs one reader task, and hands out independent [`Subscriber`]s.
+//! Each subscriber carries a local predicate applied before delivery, so
+//! consumers get their own filtered view without contending for the socket or
+//! blocking each other. This is the unbounded-channel fan-out pattern: one
+//! producer, many non-blocking consumers.
+
+use std::sync::{ Arc, Mutex };
+
+use futures::StreamExt;
+use ipnet::IpNet;
+use tokio::sync::mpsc::{ unbounded_channel, UnboundedReceiver, UnboundedSender };
+
+use crate::BGPUpdate;
+use crate::r#async::AsyncRISLiveSession;
+
+/// A per-subscriber predicate deciding which updates to deliver.
+pub type Predicate = Arc<dyn Fn(&BGPUpdate) -> bool + Send + Sync>;
+
+struct Sub {
+    tx: UnboundedSender<BGPUpdate>,
+    predicate: Predicate,
+}
+
+/// A cloneable handle to a running fan-out hub. Cloning hands out another
+/// reference to the same upstream connection; call [`BroadcastHub::subscribe`]
+/// to register a new consumer.
+#[derive(Clone)]
+pub struct BroadcastHub {
+    subs: Arc<Mutex<Vec<Sub>>>,
+}
+impl BroadcastHub {
+    /// Spawn the reader task that drains `session` and fans each parsed
+    /// [`BGPUpdate`] out to every matching subscriber.
+    pub fn spawn(mut session: AsyncRISLiveSession) -> Self {
+        let subs: Arc<Mutex<Vec<Sub>>> = Arc::new(Mutex::new(Vec::new()));
+        let reader = subs.clone();
+        tokio::spawn(async move {
+            while let Some(item) = session.next().await {
+                let update = match item {
+                    Ok(u) => u,
+                    // A parse/socket error on one frame shouldn't tear down
+                    // every subscriber; skip it and keep reading.
+                    Err(_) => continue,
+                };
+                let mut guard = reader.lock().unwrap();
+                // Drop subscribers whose receiver has been closed.
+                guard.retain(|s| {
+                    if (s.predicate)(&update) {
+                        s.tx.send(update.clone()).is_ok()
+                    } else {
+                        !s.tx.is_closed()
+                    }
+                });
+            }
+        });
+        Self { subs }
+    }
+
+    /// Register a consumer receiving only the updates matching `predicate`.
+    pub fn subscribe(&self, predicate: Predicate) -> Subscriber {
+        let (tx, rx) = unbounded_channel();
+        self.subs.lock().unwrap().push(Sub { tx, predicate });
+        Subscriber { rx }
+    }
+
+    /// Register a consumer receiving every update.
+    pub fn subscribe_all(&self) -> Subscriber {
+        self.subscribe(Arc::new(|_| true))
+    }
+}
+
+/// A single consumer's filtered view of the firehose.
+pub struct Subscriber {
+    rx: UnboundedReceiver<BGPUpdate>,
+}
+impl Subscriber {
+    /// Await the next matching update, or `None` once the upstream session
+    /// has ended and every [`BroadcastHub`] handle has been dropped.
+    pub async fn recv(&mut self) -> Option<BGPUpdate> {
+        self.rx.recv().await
+    }
+}
+
+/// A predicate matching updates with `asn` somewhere in the AS path.
+pub fn asn_in_path(asn: u32) -> Predicate {
+    Arc::new(move |u| u.path_contains(asn))
+}
+
+/// A predicate matching updates touching a prefix contained within `net`.
+pub fn prefix_within(net: IpNet) -> Predicate {
+    Arc::new(move |u| u.prefixes().iter().any(|p| net.contains(p)))
+}
@@ -1,5 +1,11 @@
 
 pub mod parse;
+pub mod bgp;
+pub mod resilient;
+#[cfg(feature="async")]
+pub mod r#async;
+#[cfg(feature="async")]
+pub mod hub;
 
 use std::net::TcpStream;
 use tungstenite::stream::MaybeTlsStream;
@@ -7,6 +13,7 @@ use ipnet::IpNet;
 use std::net::IpAddr;
 use url::Url;
 use itertools::Itertools;
+use serde::Serialize;
 
 use crate::parse::{ RISMessage, RISMessageType };
 
@@ -15,6 +22,40 @@ pub const RIS_URL: &'static str = {
     "ws://ris-live.ripe.net/v1/ws/?client=ris-rs"
 };
 
+/// Errors surfaced while talking to RIS Live.
+#[derive(Debug)]
+pub enum RisError {
+    /// Failed to establish the WebSocket connection.
+    Connect(tungstenite::Error),
+    /// An error on an already-open socket (read, write, or close).
+    WebSocket(tungstenite::Error),
+    /// Failed to deserialize a RIS Live JSON message.
+    Json(serde_json::Error),
+    /// The upstream closed the connection.
+    Closed,
+}
+impl std::fmt::Display for RisError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RisError::Connect(e) => write!(f, "couldn't connect to RIS Live: {}", e),
+            RisError::WebSocket(e) => write!(f, "websocket error: {}", e),
+            RisError::Json(e) => write!(f, "couldn't parse RIS Live message: {}", e),
+            RisError::Closed => write!(f, "connection closed by upstream"),
+        }
+    }
+}
+impl std::error::Error for RisError {}
+impl From<serde_json::Error> for RisError {
+    fn from(e: serde_json::Error) -> Self {
+        RisError::Json(e)
+    }
+}
+impl From<tungstenite::Error> for RisError {
+    fn from(e: tungstenite::Error) -> Self {
+        RisError::WebSocket(e)
+    }
+}
+
 /// Representing an active RIS Live session.
 pub struct RISLiveSession {
     sock: tungstenite::protocol::WebSocket<MaybeTlsStream<TcpStream>>,
@@ -27,9 +68,35 @@ impl RISLiveSession {
         Self { sock }
     }
 
-    /// Close this session
+    /// Create a new session, returning an error instead of panicking if the
+    /// connection can't be established.
+    pub fn try_new() -> Result<Self, RisError> {
+        let (sock, _) = tungstenite::connect(Url::parse(RIS_URL).unwrap())
+            .map_err(RisError::Connect)?;
+        Ok(Self { sock })
+    }
+
+    /// Close this session. A close on an already-broken socket is expected to
+    /// fail; the error is ignored so tearing down a dead session never panics.
     pub fn close(&mut self) {
-        self.sock.close(None).unwrap();
+        let _ = self.sock.close(None);
+    }
+
+    /// Write a raw text frame to the socket, surfacing any error.
+    pub fn send_raw(&mut self, text: String) -> Result<(), RisError> {
+        self.sock.write_message(tungstenite::Message::Text(text))?;
+        Ok(())
+    }
+
+    /// Read the next text frame, returning `Ok(None)` for non-text frames and
+    /// an error instead of panicking on a socket failure.
+    pub fn try_read_msg(&mut self) -> Result<Option<String>, RisError> {
+        let msg = self.sock.read_message()?;
+        Ok(if let tungstenite::Message::Text(s) = msg {
+            Some(s)
+        } else {
+            None
+        })
     }
 
     /// Subscribe to all withdrawal messages.
@@ -42,6 +109,19 @@ impl RISLiveSession {
         )).unwrap()
     }
 
+    /// Push a fully-specified [`SubscriptionFilter`] to the server as a
+    /// single `ris_subscribe` message. This lets the collector do the
+    /// filtering instead of receiving the whole firehose and discarding
+    /// unwanted messages locally.
+    pub fn subscribe(&mut self, filter: &SubscriptionFilter) {
+        self.sock.write_message(tungstenite::Message::Text(
+            serde_json::json!({
+                "type": "ris_subscribe",
+                "data": filter,
+            }).to_string()
+        )).unwrap()
+    }
+
     /// Given a list of AS numbers, subscribe to updates where each ASN
     /// is present in the path.
     pub fn subscribe_asn_list(&mut self, path_list: &[u32]) {
@@ -69,20 +149,121 @@ impl std::ops::Drop for RISLiveSession {
     }
 }
 
-#[derive(Debug)]
+/// The message classes RIS Live can filter a subscription down to.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[allow(non_camel_case_types)]
+pub enum SubscribeMessageType {
+    UPDATE,
+    OPEN,
+    NOTIFICATION,
+    KEEPALIVE,
+    RIS_PEER_STATE,
+}
+
+/// Per-subscription socket options accepted by RIS Live.
+#[derive(Debug, Default, Serialize)]
+struct SocketOptions {
+    #[serde(rename="includeRaw", skip_serializing_if="Option::is_none")]
+    include_raw: Option<bool>,
+}
+
+/// Builder for the `data` object of a `ris_subscribe` message.
+///
+/// Every field RIS Live accepts can be set here and composed into a single
+/// subscription; absent fields are omitted from the serialized JSON rather
+/// than sent as `null`. The `moreSpecific`/`lessSpecific` booleans are only
+/// emitted alongside a `prefix`.
+#[derive(Debug, Default, Serialize)]
+pub struct SubscriptionFilter {
+    #[serde(skip_serializing_if="Option::is_none")]
+    host: Option<String>,
+    #[serde(rename="type", skip_serializing_if="Option::is_none")]
+    ty: Option<SubscribeMessageType>,
+    #[serde(skip_serializing_if="Option::is_none")]
+    require: Option<String>,
+    #[serde(skip_serializing_if="Option::is_none")]
+    peer: Option<IpAddr>,
+    #[serde(skip_serializing_if="Option::is_none")]
+    prefix: Option<String>,
+    #[serde(rename="moreSpecific", skip_serializing_if="Option::is_none")]
+    more_specific: Option<bool>,
+    #[serde(rename="lessSpecific", skip_serializing_if="Option::is_none")]
+    less_specific: Option<bool>,
+    #[serde(skip_serializing_if="Option::is_none")]
+    path: Option<String>,
+    #[serde(rename="socketOptions", skip_serializing_if="Option::is_none")]
+    socket_options: Option<SocketOptions>,
+}
+impl SubscriptionFilter {
+    /// An empty filter matching every message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit the subscription to a single collector (e.g. `rrc21`).
+    pub fn host(mut self, host: &str) -> Self {
+        self.host = Some(host.to_owned());
+        self
+    }
+
+    /// Limit the subscription to a single BGP message class.
+    pub fn message_type(mut self, ty: SubscribeMessageType) -> Self {
+        self.ty = Some(ty);
+        self
+    }
+
+    /// Require either `"announcements"` or `"withdrawals"` to be present.
+    pub fn require(mut self, require: &str) -> Self {
+        self.require = Some(require.to_owned());
+        self
+    }
+
+    /// Limit the subscription to a single peer IP.
+    pub fn peer(mut self, peer: IpAddr) -> Self {
+        self.peer = Some(peer);
+        self
+    }
+
+    /// Match a prefix, optionally including more- and/or less-specific
+    /// prefixes. The two booleans are only sent because a prefix is set.
+    pub fn prefix(mut self, prefix: &str, more_specific: bool,
+        less_specific: bool) -> Self
+    {
+        self.prefix = Some(prefix.to_owned());
+        self.more_specific = Some(more_specific);
+        self.less_specific = Some(less_specific);
+        self
+    }
+
+    /// Match updates whose AS path satisfies the given regular expression.
+    pub fn path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_owned());
+        self
+    }
+
+    /// Ask the server to include the raw BGP PDU (as hex) on each message.
+    pub fn include_raw(mut self, include_raw: bool) -> Self {
+        self.socket_options = Some(SocketOptions {
+            include_raw: Some(include_raw),
+        });
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct AnnouncementVector {
     pub next_hop: IpAddr,
     pub prefixes: Vec<IpNet>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum BGPUpdateType {
     Announce { path: Vec<u32>, vectors: Vec<AnnouncementVector> },
     Withdraw { prefixes: Vec<IpNet> },
 }
 
 /// Representing a BGP update message.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BGPUpdate {
     timestamp: f64,
     asn: u32,
@@ -133,6 +314,96 @@ impl BGPUpdate {
         }
         None
     }
+
+    /// The peer ASN that sent this update.
+    pub fn asn(&self) -> u32 {
+        self.asn
+    }
+
+    /// The timestamp RIS Live assigned to this update.
+    pub fn timestamp(&self) -> f64 {
+        self.timestamp
+    }
+
+    /// Whether `asn` appears in the AS path (announcements only; always
+    /// `false` for withdrawals, which carry no path).
+    pub fn path_contains(&self, asn: u32) -> bool {
+        match &self.kind {
+            BGPUpdateType::Announce { path, .. } => path.contains(&asn),
+            BGPUpdateType::Withdraw { .. } => false,
+        }
+    }
+
+    /// The set of prefixes this update touches, whether announced or
+    /// withdrawn.
+    pub fn prefixes(&self) -> Vec<IpNet> {
+        match &self.kind {
+            BGPUpdateType::Announce { vectors, .. } =>
+                vectors.iter().flat_map(|v| v.prefixes.iter().copied()).collect(),
+            BGPUpdateType::Withdraw { prefixes } => prefixes.to_owned(),
+        }
+    }
+}
+
+/// A single flattened announcement record: one per `(prefix, path)` pair.
+///
+/// Unlike [`BGPUpdate`], which keeps only the path and reachability vectors,
+/// this carries the full set of path attributes RIS Live exposes so that
+/// downstream analytics (community tagging, origin changes, &c.) don't need
+/// to re-fetch the raw message.
+#[derive(Debug)]
+pub struct BgpElem {
+    pub timestamp: f64,
+    pub peer_asn: u32,
+    pub prefix: IpNet,
+    pub next_hop: IpAddr,
+    pub origin: Option<String>,
+    pub path: Vec<u32>,
+    pub communities: Vec<(u32, u32)>,
+    pub med: Option<u32>,
+    pub aggregator: Option<String>,
+    pub atomic_aggregate: Option<bool>,
+}
+impl BgpElem {
+    /// Flatten an announcement [`RISMessage`] into one element per prefix.
+    ///
+    /// Returns an empty `Vec` for messages without announcements (e.g. pure
+    /// withdrawals, or non-UPDATE messages).
+    pub fn from_message(msg: &RISMessage) -> Vec<Self> {
+        let mut res = Vec::new();
+        if let RISMessageType::UPDATE { announce, .. } = &msg.ty {
+            let (path, vectors) = match (&announce.path, &announce.announcements) {
+                (Some(path), Some(vectors)) => (path, vectors),
+                _ => return res,
+            };
+            let peer_asn = u32::from_str_radix(&msg.peer_asn, 10).unwrap();
+            let communities: Vec<(u32, u32)> = announce.community.iter()
+                .flatten()
+                .filter_map(|c| match c.as_slice() {
+                    [asn, value] => Some((*asn, *value)),
+                    _ => None,
+                })
+                .collect();
+            for v in vectors {
+                let next_hop: IpAddr = v.next_hop.parse().unwrap();
+                for p in &v.prefixes {
+                    res.push(BgpElem {
+                        timestamp: msg.timestamp,
+                        peer_asn,
+                        prefix: p.parse().unwrap(),
+                        next_hop,
+                        origin: announce.origin.to_owned(),
+                        path: path.to_owned(),
+                        communities: communities.to_owned(),
+                        med: announce.med,
+                        aggregator: announce.aggregator.to_owned(),
+                        atomic_aggregate: announce.atomic_aggregate,
+                    });
+                }
+            }
+        }
+        res
+    }
 }
 
 impl std::fmt::Display for BGPUpdate {
@@ -19,10 +19,21 @@ pub struct RISMessage {
     pub id: String,
     pub host: String,
 
+    /// The raw BGP PDU as a hex string, present when the subscription was
+    /// made with `socketOptions.includeRaw`.
+    pub raw: Option<String>,
+
     // NOTE: Flattening is kind of annoying, but it seems like it works?
     #[serde(rename="type", flatten)]
     pub ty: RISMessageType,
 }
+impl RISMessage {
+    /// Decode the raw BGP PDU, if this message carries one. Returns `None`
+    /// when the message was delivered without `socketOptions.includeRaw`.
+    pub fn decode_raw(&self) -> Option<Result<crate::bgp::BgpPdu, crate::bgp::BgpDecodeError>> {
+        self.raw.as_ref().map(|hex| crate::bgp::decode_hex(hex))
+    }
+}
 
 /// JSON format for different kinds of BGP messages.
 ///
@@ -36,6 +47,13 @@ pub enum RISMessageType {
         announce: RISAnnouncement,
         withdrawals: Option<Vec<String>>,
     },
+    OPEN {},
+    NOTIFICATION {},
+    KEEPALIVE {},
+    RIS_PEER_STATE {
+        /// The new peer state, e.g. `"connected"` or `"down"`.
+        state: Option<String>,
+    },
 }
 
 /// JSON format for a set of announcements.
@@ -44,6 +62,9 @@ pub struct RISAnnouncement {
     pub path: Option<Vec<u32>>,
     pub community: Option<Vec<Vec<u32>>>,
     pub origin: Option<String>,
+    pub med: Option<u32>,
+    pub aggregator: Option<String>,
+    pub atomic_aggregate: Option<bool>,
     pub announcements: Option<Vec<AnnouncementEntry>>,
 }
 
@@ -0,0 +1,139 @@
+//! A self-healing wrapper around [`RISLiveSession`].
+//!
+//! The bare session `expect`/`unwrap`s on connect and read, so any network
+//! blip or TLS drop takes down the whole program. [`ResilientSession`] catches
+//! disconnects, reconnects to [`RIS_URL`] with exponential backoff, and
+//! replays every subscription it previously issued so the server resumes
+//! delivering the same stream. It also keeps the last N parsed updates and the
+//! last seen timestamp so callers can detect gaps across a reconnect.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::{ RISLiveSession, RisError, SubscriptionFilter, BGPUpdate };
+use crate::parse::RISPacket;
+
+/// Smallest backoff delay between reconnection attempts.
+const BACKOFF_MIN: Duration = Duration::from_secs(1);
+/// Largest backoff delay between reconnection attempts.
+const BACKOFF_MAX: Duration = Duration::from_secs(60);
+
+/// A [`RISLiveSession`] that transparently re-establishes and re-subscribes.
+pub struct ResilientSession {
+    session: RISLiveSession,
+    /// The `ris_subscribe` payloads sent so far, replayed verbatim on every
+    /// reconnect.
+    subscriptions: Vec<String>,
+    /// Ring buffer of the most recently parsed updates.
+    buffer: VecDeque<BGPUpdate>,
+    buffer_cap: usize,
+    /// The `timestamp` of the last update seen, to spot gaps across a drop.
+    last_timestamp: Option<f64>,
+}
+impl ResilientSession {
+    /// Connect, retrying with exponential backoff until successful.
+    ///
+    /// `buffer_cap` bounds how many recent updates are retained; pass `0` to
+    /// disable buffering.
+    pub fn new(buffer_cap: usize) -> Self {
+        let session = Self::connect_with_backoff();
+        Self {
+            session,
+            subscriptions: Vec::new(),
+            buffer: VecDeque::new(),
+            buffer_cap,
+            last_timestamp: None,
+        }
+    }
+
+    /// Issue a subscription and remember it for replay on reconnect.
+    pub fn subscribe(&mut self, filter: &SubscriptionFilter) {
+        let payload = serde_json::json!({
+            "type": "ris_subscribe",
+            "data": filter,
+        }).to_string();
+        self.send_subscription(payload);
+    }
+
+    /// Read and parse the next frame's worth of updates, reconnecting and
+    /// replaying subscriptions transparently if the socket drops.
+    pub fn next_updates(&mut self) -> Vec<BGPUpdate> {
+        loop {
+            match self.session.try_read_msg() {
+                Ok(Some(s)) => {
+                    if let Ok(RISPacket::Message(m)) =
+                        serde_json::from_str::<RISPacket>(&s)
+                    {
+                        if let Some(updates) = BGPUpdate::from_message(&m) {
+                            for u in &updates {
+                                self.last_timestamp = Some(u.timestamp());
+                                if self.buffer_cap > 0 {
+                                    if self.buffer.len() == self.buffer_cap {
+                                        self.buffer.pop_front();
+                                    }
+                                    self.buffer.push_back(u.clone());
+                                }
+                            }
+                            return updates;
+                        }
+                    }
+                    // Unparseable, non-message or non-UPDATE frame: keep reading.
+                },
+                // Non-text frame: keep reading.
+                Ok(None) => {},
+                Err(_) => self.reconnect(),
+            }
+        }
+    }
+
+    /// The `timestamp` of the most recently seen update, if any.
+    pub fn last_timestamp(&self) -> Option<f64> {
+        self.last_timestamp
+    }
+
+    /// The buffered recent updates, oldest first.
+    pub fn buffered(&self) -> impl Iterator<Item = &BGPUpdate> {
+        self.buffer.iter()
+    }
+
+    fn send_subscription(&mut self, payload: String) {
+        // Retry through reconnects until the subscription lands.
+        while self.session.send_raw(payload.clone()).is_err() {
+            self.reconnect();
+        }
+        self.subscriptions.push(payload);
+    }
+
+    /// Re-establish the connection and replay every prior subscription.
+    ///
+    /// Replay must be all-or-nothing: if a `send_raw` fails partway through,
+    /// the socket is dead and the subscriptions already sent are lost with it,
+    /// so we reconnect and restart the whole set rather than resuming from the
+    /// failing index. Each attempt runs against a fresh connection until one
+    /// full pass lands every subscription.
+    fn reconnect(&mut self) {
+        self.session = Self::connect_with_backoff();
+        'replay: loop {
+            for payload in &self.subscriptions {
+                if self.session.send_raw(payload.clone()).is_err() {
+                    self.session = Self::connect_with_backoff();
+                    continue 'replay;
+                }
+            }
+            break;
+        }
+    }
+
+    fn connect_with_backoff() -> RISLiveSession {
+        let mut delay = BACKOFF_MIN;
+        loop {
+            match RISLiveSession::try_new() {
+                Ok(session) => return session,
+                Err(_) => {
+                    std::thread::sleep(delay);
+                    delay = (delay * 2).min(BACKOFF_MAX);
+                },
+            }
+        }
+    }
+}